@@ -7,19 +7,77 @@ macro_rules! pushrep {
 }
 
 pub mod grid_print {
-    use std::io::Write;
-    pub use termcolor::Color;
-    use termcolor::{BufferWriter, ColorChoice, ColorSpec, WriteColor};
+    pub use termcolor::{Color, ColorChoice, WriteColor};
+    use termcolor::{BufferWriter, ColorSpec};
+
+    /// Display width of a single character, in terminal columns, following the
+    /// `wcwidth` convention: combining marks and other zero-width codepoints
+    /// take no columns, East-Asian wide/fullwidth codepoints take two, and
+    /// everything else takes one.
+    fn char_display_width(char: char) -> usize {
+        if char.is_control() {
+            return 0;
+        }
+        let cp = char as u32;
+        if matches!(cp,
+            0x0300..=0x036F
+                | 0x0483..=0x0489
+                | 0x0591..=0x05BD
+                | 0x200B..=0x200F
+                | 0x202A..=0x202E
+                | 0x20D0..=0x20FF
+                | 0xFE00..=0xFE0F
+                | 0xFE20..=0xFE2F
+                | 0xFEFF
+        ) {
+            return 0;
+        }
+        if matches!(cp,
+            0x1100..=0x115F
+                | 0x2E80..=0x303E
+                | 0x3041..=0x33FF
+                | 0x3400..=0x4DBF
+                | 0x4E00..=0x9FFF
+                | 0xA000..=0xA4CF
+                | 0xAC00..=0xD7A3
+                | 0xF900..=0xFAFF
+                | 0xFE30..=0xFE4F
+                | 0xFF00..=0xFF60
+                | 0xFFE0..=0xFFE6
+                | 0x1F300..=0x1F64F
+                | 0x1F900..=0x1F9FF
+                | 0x20000..=0x3FFFD
+        ) {
+            return 2;
+        }
+        1
+    }
+
+    /// Display width of a string in terminal columns (see [`char_display_width`]).
+    pub fn display_width(string: &str) -> usize {
+        string.chars().map(char_display_width).sum()
+    }
 
     #[derive(Clone, Copy)]
     pub struct ColoredChar {
         char: char,
         color: Option<Color>,
+        bg_color: Option<Color>,
+        bold: bool,
+        underline: bool,
+        inverse: bool,
     }
 
     impl ColoredChar {
         pub fn new(char: char) -> Self {
-            Self { char, color: None }
+            Self {
+                char,
+                color: None,
+                bg_color: None,
+                bold: false,
+                underline: false,
+                inverse: false,
+            }
         }
 
         pub fn color(mut self, color: Option<Color>) -> Self {
@@ -27,10 +85,60 @@ pub mod grid_print {
             self
         }
 
-        pub fn apply_default_color(&mut self, color: Option<Color>) {
+        pub fn bg_color(mut self, bg_color: Option<Color>) -> Self {
+            self.bg_color = bg_color;
+            self
+        }
+
+        pub fn bold(mut self, bold: bool) -> Self {
+            self.bold = bold;
+            self
+        }
+
+        pub fn underline(mut self, underline: bool) -> Self {
+            self.underline = underline;
+            self
+        }
+
+        pub fn inverse(mut self, inverse: bool) -> Self {
+            self.inverse = inverse;
+            self
+        }
+
+        pub fn apply_default_style(
+            &mut self,
+            color: Option<Color>,
+            bg_color: Option<Color>,
+            bold: bool,
+            underline: bool,
+            inverse: bool,
+        ) {
             if self.color.is_none() {
                 self.color = color;
             }
+            if self.bg_color.is_none() {
+                self.bg_color = bg_color;
+            }
+            self.bold = self.bold || bold;
+            self.underline = self.underline || underline;
+            self.inverse = self.inverse || inverse;
+        }
+
+        /// Builds the `termcolor::ColorSpec` for this character. `termcolor`
+        /// has no native reverse-video flag, so inverse is simulated by
+        /// swapping fg/bg, defaulting the unset side to black/white.
+        fn color_spec(&self) -> ColorSpec {
+            let mut spec = ColorSpec::new();
+            if self.inverse {
+                spec.set_fg(Some(self.bg_color.unwrap_or(Color::Black)));
+                spec.set_bg(Some(self.color.unwrap_or(Color::White)));
+            } else {
+                spec.set_fg(self.color);
+                spec.set_bg(self.bg_color);
+            }
+            spec.set_bold(self.bold);
+            spec.set_underline(self.underline);
+            spec
         }
     }
 
@@ -56,6 +164,34 @@ pub mod grid_print {
             self
         }
 
+        pub fn set_bg(mut self, bg_color: Color) -> Self {
+            for char in self.chars.iter_mut() {
+                char.bg_color = Some(bg_color);
+            }
+            self
+        }
+
+        pub fn set_bold(mut self, bold: bool) -> Self {
+            for char in self.chars.iter_mut() {
+                char.bold = bold;
+            }
+            self
+        }
+
+        pub fn set_underline(mut self, underline: bool) -> Self {
+            for char in self.chars.iter_mut() {
+                char.underline = underline;
+            }
+            self
+        }
+
+        pub fn set_inverse(mut self, inverse: bool) -> Self {
+            for char in self.chars.iter_mut() {
+                char.inverse = inverse;
+            }
+            self
+        }
+
         pub fn from_c(string: &str, color: Option<Color>) -> Self {
             Self {
                 chars: string
@@ -107,22 +243,315 @@ pub mod grid_print {
             self.chars.extend_from_slice(&string.chars);
         }
 
-        pub fn apply_default_color(&mut self, color: Option<Color>) {
+        /// Display width of this string in terminal columns, counting wide
+        /// characters as two columns and zero-width characters as none,
+        /// instead of `chars().count()`.
+        pub fn display_width(&self) -> usize {
+            self.chars.iter().map(|c| char_display_width(c.char)).sum()
+        }
+
+        pub fn apply_default_style(
+            &mut self,
+            color: Option<Color>,
+            bg_color: Option<Color>,
+            bold: bool,
+            underline: bool,
+            inverse: bool,
+        ) {
             for char in &mut self.chars {
-                char.apply_default_color(color);
+                char.apply_default_style(color, bg_color, bold, underline, inverse);
             }
         }
 
-        pub fn print(&mut self, bufwtr: &mut BufferWriter, buffer: &mut termcolor::Buffer) {
+        /// Writes this string into any `WriteColor` sink. Adjacent characters
+        /// sharing the same color/style are coalesced into a single write, so
+        /// `set_color` is only called when the style actually changes instead
+        /// of once per character.
+        pub fn write_to<W: WriteColor>(&self, w: &mut W) {
+            let mut active: Option<ColorSpec> = None;
+            let mut run = String::new();
             for char in &self.chars {
-                let mut spec = ColorSpec::new();
-                spec.set_fg(char.color);
-                buffer.set_color(&spec).unwrap();
-                buffer.write_all(char.char.to_string().as_bytes()).unwrap();
+                let spec = char.color_spec();
+                if active.as_ref() != Some(&spec) {
+                    if !run.is_empty() {
+                        w.write_all(run.as_bytes()).unwrap();
+                        run.clear();
+                    }
+                    w.set_color(&spec).unwrap();
+                    active = Some(spec);
+                }
+                run.push(char.char);
+            }
+            if !run.is_empty() {
+                w.write_all(run.as_bytes()).unwrap();
+            }
+            w.reset().unwrap();
+            w.flush().unwrap();
+        }
+    }
+
+    /// Horizontal alignment of a column's content within its padded width.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum Alignment {
+        Left,
+        Center,
+        Right,
+    }
+
+    /// The glyphs `Grid::print` draws the frame with, so the box-drawing set
+    /// can be swapped (plain ASCII, light-only, ...) without touching layout.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Borders {
+        /// Inner column separator, and the border around the x-label row.
+        pub vertical: char,
+        /// Outer left/right border of the grid body.
+        pub vertical_heavy: char,
+        /// Light horizontal rule between data rows, and under the y-label
+        /// column.
+        pub horizontal: char,
+        /// Heavy horizontal rule used for the outer frame and header rule.
+        pub horizontal_heavy: char,
+
+        /// Top-left corner when there are no labels at all.
+        pub top_left: char,
+        /// Top-left corner when there are y-labels but no x-labels.
+        pub top_left_with_y_labels: char,
+        pub top_mid: char,
+        pub top_right: char,
+
+        /// Left end of the rule below the x-labels, with a y-label column.
+        pub header_left: char,
+        /// Left end of the rule below the x-labels, without a y-label column.
+        pub header_left_no_y_labels: char,
+        pub header_mid: char,
+        pub header_right: char,
+
+        /// Left end of a rule between two data rows, with a y-label column.
+        pub row_left: char,
+        /// Left end of a rule between two data rows, without a y-label column.
+        pub row_left_no_y_labels: char,
+        pub row_mid: char,
+        pub row_right: char,
+
+        /// Bottom-left corner, with a y-label column.
+        pub bottom_left: char,
+        /// Bottom-left corner, without a y-label column.
+        pub bottom_left_no_y_labels: char,
+        pub bottom_mid: char,
+        pub bottom_right: char,
+    }
+
+    impl Borders {
+        /// The box-drawing set `Grid::print` has always used: heavy outer
+        /// frame and header rule, light inner separators.
+        pub fn heavy() -> Self {
+            Self {
+                vertical: '│',
+                vertical_heavy: '┃',
+                horizontal: '─',
+                horizontal_heavy: '━',
+
+                top_left: '┏',
+                top_left_with_y_labels: '┲',
+                top_mid: '┯',
+                top_right: '┓',
+
+                header_left: '╆',
+                header_left_no_y_labels: '┢',
+                header_mid: '┿',
+                header_right: '┪',
+
+                row_left: '╂',
+                row_left_no_y_labels: '┠',
+                row_mid: '┼',
+                row_right: '┨',
+
+                bottom_left: '┺',
+                bottom_left_no_y_labels: '┗',
+                bottom_mid: '┷',
+                bottom_right: '┛',
+            }
+        }
+
+        /// Plain `+ - |` borders for terminals without UTF-8 box-drawing.
+        pub fn ascii() -> Self {
+            Self {
+                vertical: '|',
+                vertical_heavy: '|',
+                horizontal: '-',
+                horizontal_heavy: '-',
+
+                top_left: '+',
+                top_left_with_y_labels: '+',
+                top_mid: '+',
+                top_right: '+',
+
+                header_left: '+',
+                header_left_no_y_labels: '+',
+                header_mid: '+',
+                header_right: '+',
+
+                row_left: '+',
+                row_left_no_y_labels: '+',
+                row_mid: '+',
+                row_right: '+',
+
+                bottom_left: '+',
+                bottom_left_no_y_labels: '+',
+                bottom_mid: '+',
+                bottom_right: '+',
+            }
+        }
+
+        /// Uniform light box-drawing lines, with no heavy/light frame split.
+        pub fn light() -> Self {
+            Self {
+                vertical: '│',
+                vertical_heavy: '│',
+                horizontal: '─',
+                horizontal_heavy: '─',
+
+                top_left: '┌',
+                top_left_with_y_labels: '┬',
+                top_mid: '┬',
+                top_right: '┐',
+
+                header_left: '┼',
+                header_left_no_y_labels: '├',
+                header_mid: '┼',
+                header_right: '┤',
+
+                row_left: '┼',
+                row_left_no_y_labels: '├',
+                row_mid: '┼',
+                row_right: '┤',
+
+                bottom_left: '┴',
+                bottom_left_no_y_labels: '└',
+                bottom_mid: '┴',
+                bottom_right: '┘',
+            }
+        }
+    }
+
+    impl Default for Borders {
+        fn default() -> Self {
+            Self::heavy()
+        }
+    }
+
+    /// How a cell's content is handled once its column has been shrunk
+    /// narrower than the content's display width.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum Overflow {
+        /// Cut the content to fit, ending it with an ellipsis.
+        Truncate,
+        /// Break the content onto additional physical lines at word
+        /// boundaries, expanding the row's height.
+        Wrap,
+    }
+
+    fn chars_display_width(chars: &[ColoredChar]) -> usize {
+        chars.iter().map(|c| char_display_width(c.char)).sum()
+    }
+
+    /// Truncates `chars` to fit in `width` display columns, replacing the
+    /// cut-off tail with a single `…`, styled like the last character kept.
+    fn truncate_with_ellipsis(chars: &[ColoredChar], width: usize) -> Vec<ColoredChar> {
+        if width == 0 {
+            return vec![];
+        }
+        let mut out = vec![];
+        let mut used = 0;
+        let mut style = chars.first().copied();
+        for c in chars {
+            let w = char_display_width(c.char);
+            if used + w > width - 1 {
+                break;
+            }
+            used += w;
+            style = Some(*c);
+            out.push(*c);
+        }
+        out.push(match style {
+            Some(c) => ColoredChar { char: '…', ..c },
+            None => ColoredChar::new('…'),
+        });
+        out
+    }
+
+    /// Word-wraps `chars` into lines of at most `width` display columns.
+    /// A single word wider than `width` is truncated with an ellipsis rather
+    /// than overflowing the column.
+    fn wrap_chars(chars: &[ColoredChar], width: usize) -> Vec<Vec<ColoredChar>> {
+        if width == 0 {
+            return vec![vec![]];
+        }
+        let mut lines = vec![];
+        let mut line: Vec<ColoredChar> = vec![];
+        let mut line_width = 0;
+        let mut i = 0;
+        while i < chars.len() {
+            let word_start = i;
+            while i < chars.len() && chars[i].char != ' ' {
+                i += 1;
+            }
+            let word = &chars[word_start..i];
+            while i < chars.len() && chars[i].char == ' ' {
+                i += 1;
+            }
+            if word.is_empty() {
+                continue;
+            }
+            let word_width = chars_display_width(word);
+            if word_width > width {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                lines.push(truncate_with_ellipsis(word, width));
+                continue;
+            }
+            if line_width > 0 && line_width + 1 + word_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            } else if line_width > 0 {
+                line.push(ColoredChar::new(' '));
+                line_width += 1;
+            }
+            line.extend_from_slice(word);
+            line_width += word_width;
+        }
+        if !line.is_empty() || lines.is_empty() {
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Fits `chars` into `width` display columns, returning the physical
+    /// lines the cell renders as. Content already narrow enough is returned
+    /// unchanged as a single line.
+    fn shrink_cell(chars: &[ColoredChar], width: usize, overflow: Overflow) -> Vec<Vec<ColoredChar>> {
+        if chars_display_width(chars) <= width {
+            return vec![chars.to_vec()];
+        }
+        match overflow {
+            Overflow::Truncate => vec![truncate_with_ellipsis(chars, width)],
+            Overflow::Wrap => wrap_chars(chars, width),
+        }
+    }
+
+    /// Splits `diff` slack columns into a (pad_a, pad_b) pair for the given
+    /// alignment, where pad_a is printed before the content and pad_b after.
+    fn align_padding(alignment: Alignment, diff: usize) -> (usize, usize) {
+        match alignment {
+            Alignment::Left => (0, diff),
+            Alignment::Right => (diff, 0),
+            Alignment::Center => {
+                let pad_a = diff / 2;
+                let pad_b = if diff % 2 == 0 { pad_a } else { pad_a + 1 };
+                (pad_a, pad_b)
             }
-            bufwtr.print(&buffer).unwrap();
-            buffer.reset().unwrap();
-            buffer.flush().unwrap();
         }
     }
 
@@ -130,13 +559,30 @@ pub mod grid_print {
         x_labels: Vec<ColoredString>,
         y_labels: Vec<ColoredString>,
         static_column_width: bool,
+        column_alignment: Vec<Alignment>,
+        default_alignment: Alignment,
+        borders: Borders,
+        max_width: Option<usize>,
+        overflow: Overflow,
         draw_x_labels: bool,
         draw_y_labels: bool,
         grid: Vec<Vec<ColoredString>>,
         line_color: Option<Color>,
         x_label_color: Option<Color>,
+        x_label_bg: Option<Color>,
+        x_label_bold: bool,
+        x_label_underline: bool,
+        x_label_inverse: bool,
         y_label_color: Option<Color>,
+        y_label_bg: Option<Color>,
+        y_label_bold: bool,
+        y_label_underline: bool,
+        y_label_inverse: bool,
         cell_color: Option<Color>,
+        cell_bg: Option<Color>,
+        cell_bold: bool,
+        cell_underline: bool,
+        cell_inverse: bool,
     }
 
     impl Grid {
@@ -145,13 +591,30 @@ pub mod grid_print {
                 x_labels: vec![],
                 y_labels: vec![],
                 static_column_width: false,
+                column_alignment: vec![],
+                default_alignment: Alignment::Center,
+                borders: Borders::heavy(),
+                max_width: None,
+                overflow: Overflow::Truncate,
                 draw_x_labels: true,
                 draw_y_labels: true,
                 grid: vec![],
                 line_color: None,
                 x_label_color: None,
+                x_label_bg: None,
+                x_label_bold: false,
+                x_label_underline: false,
+                x_label_inverse: false,
                 y_label_color: None,
+                y_label_bg: None,
+                y_label_bold: false,
+                y_label_underline: false,
+                y_label_inverse: false,
                 cell_color: None,
+                cell_bg: None,
+                cell_bold: false,
+                cell_underline: false,
+                cell_inverse: false,
             }
         }
 
@@ -165,21 +628,124 @@ pub mod grid_print {
             self
         }
 
+        pub fn set_x_label_bg(mut self, color: Color) -> Self {
+            self.x_label_bg = Some(color);
+            self
+        }
+
+        pub fn set_x_label_bold(mut self, bold: bool) -> Self {
+            self.x_label_bold = bold;
+            self
+        }
+
+        pub fn set_x_label_underline(mut self, underline: bool) -> Self {
+            self.x_label_underline = underline;
+            self
+        }
+
+        pub fn set_x_label_inverse(mut self, inverse: bool) -> Self {
+            self.x_label_inverse = inverse;
+            self
+        }
+
         pub fn set_y_label_color(mut self, color: Color) -> Self {
             self.y_label_color = Some(color);
             self
         }
 
+        pub fn set_y_label_bg(mut self, color: Color) -> Self {
+            self.y_label_bg = Some(color);
+            self
+        }
+
+        pub fn set_y_label_bold(mut self, bold: bool) -> Self {
+            self.y_label_bold = bold;
+            self
+        }
+
+        pub fn set_y_label_underline(mut self, underline: bool) -> Self {
+            self.y_label_underline = underline;
+            self
+        }
+
+        pub fn set_y_label_inverse(mut self, inverse: bool) -> Self {
+            self.y_label_inverse = inverse;
+            self
+        }
+
         pub fn set_cell_color(mut self, color: Color) -> Self {
             self.cell_color = Some(color);
             self
         }
 
+        pub fn set_cell_bg(mut self, color: Color) -> Self {
+            self.cell_bg = Some(color);
+            self
+        }
+
+        pub fn set_cell_bold(mut self, bold: bool) -> Self {
+            self.cell_bold = bold;
+            self
+        }
+
+        pub fn set_cell_underline(mut self, underline: bool) -> Self {
+            self.cell_underline = underline;
+            self
+        }
+
+        pub fn set_cell_inverse(mut self, inverse: bool) -> Self {
+            self.cell_inverse = inverse;
+            self
+        }
+
         pub fn set_static_column_width(mut self, static_column_width: bool) -> Self {
             self.static_column_width = static_column_width;
             self
         }
 
+        pub fn set_column_alignment(mut self, column_alignment: Vec<Alignment>) -> Self {
+            self.column_alignment = column_alignment;
+            self
+        }
+
+        pub fn set_default_alignment(mut self, default_alignment: Alignment) -> Self {
+            self.default_alignment = default_alignment;
+            self
+        }
+
+        fn alignment_for_column(&self, i: usize) -> Alignment {
+            *self
+                .column_alignment
+                .get(i)
+                .unwrap_or(&self.default_alignment)
+        }
+
+        pub fn set_borders(mut self, borders: Borders) -> Self {
+            self.borders = borders;
+            self
+        }
+
+        pub fn set_max_width(mut self, max_width: usize) -> Self {
+            self.max_width = Some(max_width);
+            self
+        }
+
+        pub fn set_overflow(mut self, overflow: Overflow) -> Self {
+            self.overflow = overflow;
+            self
+        }
+
+        /// The max width to lay the grid out in: the configured
+        /// `max_width`, or the detected terminal width, or 80 if neither is
+        /// available (e.g. output isn't a terminal).
+        fn effective_max_width(&self) -> usize {
+            self.max_width.unwrap_or_else(|| {
+                terminal_size::terminal_size()
+                    .map(|(terminal_size::Width(w), _)| w as usize)
+                    .unwrap_or(80)
+            })
+        }
+
         pub fn set_draw_x_labels(mut self, draw_x_labels: bool) -> Self {
             self.draw_x_labels = draw_x_labels;
             self
@@ -192,7 +758,13 @@ pub mod grid_print {
 
         pub fn set_x_labels(mut self, mut labels: Vec<ColoredString>) -> Self {
             for s in &mut labels {
-                s.apply_default_color(self.x_label_color)
+                s.apply_default_style(
+                    self.x_label_color,
+                    self.x_label_bg,
+                    self.x_label_bold,
+                    self.x_label_underline,
+                    self.x_label_inverse,
+                )
             }
             self.x_labels = labels;
             self
@@ -200,7 +772,13 @@ pub mod grid_print {
 
         pub fn set_y_labels(mut self, mut labels: Vec<ColoredString>) -> Self {
             for s in &mut labels {
-                s.apply_default_color(self.y_label_color)
+                s.apply_default_style(
+                    self.y_label_color,
+                    self.y_label_bg,
+                    self.y_label_bold,
+                    self.y_label_underline,
+                    self.y_label_inverse,
+                )
             }
             self.y_labels = labels;
             self
@@ -209,16 +787,22 @@ pub mod grid_print {
         pub fn set_grid(mut self, mut grid: Vec<Vec<ColoredString>>) -> Self {
             for row in &mut grid {
                 for s in row {
-                    s.apply_default_color(self.cell_color);
+                    s.apply_default_style(
+                        self.cell_color,
+                        self.cell_bg,
+                        self.cell_bold,
+                        self.cell_underline,
+                        self.cell_inverse,
+                    );
                 }
             }
             self.grid = grid;
             self
         }
 
-        pub fn print(&self) {
-            let mut bufwtr = BufferWriter::stdout(ColorChoice::Always);
-            let mut buffer = bufwtr.buffer();
+        /// Renders the grid into any `WriteColor` sink (a `termcolor::Buffer`,
+        /// a `StandardStream`, ...), instead of writing straight to stdout.
+        pub fn render_to<W: WriteColor>(&self, w: &mut W) {
             let mut out = ColoredString::new();
 
             //   Calculate column widths.
@@ -230,13 +814,13 @@ pub mod grid_print {
                     column_widths.push(0);
                 }
                 for j in 0..self.grid[i].len() {
-                    if self.grid[i][j].chars.len() > column_widths[i] {
-                        column_widths[i] = self.grid[i][j].chars.len();
+                    if self.grid[i][j].display_width() > column_widths[i] {
+                        column_widths[i] = self.grid[i][j].display_width();
                     }
                 }
                 if self.draw_x_labels && self.x_labels.len() > i {
-                    if self.x_labels[i].chars.len() > column_widths[i] {
-                        column_widths[i] = self.x_labels[i].chars.len();
+                    if self.x_labels[i].display_width() > column_widths[i] {
+                        column_widths[i] = self.x_labels[i].display_width();
                     }
                 }
             }
@@ -246,8 +830,39 @@ pub mod grid_print {
                 }
             }
             for n in &self.y_labels {
-                if n.chars.len() > label_width {
-                    label_width = n.chars.len();
+                if n.display_width() > label_width {
+                    label_width = n.display_width();
+                }
+            }
+
+            // Shrink the widest column(s) until the grid fits in max_width.
+            let max_width = self.effective_max_width();
+            loop {
+                let frame = if self.draw_y_labels { label_width + 2 } else { 1 };
+                let body: usize = if self.static_column_width {
+                    self.grid.len() * (largest_width + 2)
+                } else {
+                    column_widths.iter().map(|w| w + 2).sum()
+                };
+                let total = frame + body + self.grid.len().saturating_sub(1) + 1;
+                if total <= max_width {
+                    break;
+                }
+                if self.static_column_width {
+                    if largest_width <= 1 {
+                        break;
+                    }
+                    largest_width -= 1;
+                } else {
+                    let Some((idx, &w)) =
+                        column_widths.iter().enumerate().max_by_key(|(_, w)| **w)
+                    else {
+                        break;
+                    };
+                    if w <= 1 {
+                        break;
+                    }
+                    column_widths[idx] -= 1;
                 }
             }
 
@@ -256,7 +871,7 @@ pub mod grid_print {
                 if self.draw_y_labels {
                     out.push_char_rep(' ', label_width + 1);
                 }
-                out.push_char_c('│', self.line_color);
+                out.push_char_c(self.borders.vertical, self.line_color);
                 for i in 0..self.grid.len() {
                     let width;
                     if self.static_column_width {
@@ -264,25 +879,23 @@ pub mod grid_print {
                     } else {
                         width = column_widths[i];
                     }
-                    let diff = width + 2 - self.x_labels[i].chars.len();
-                    let pad_a = diff / 2;
-                    let pad_b;
-                    if diff % 2 == 0 {
-                        pad_b = pad_a;
-                    } else {
-                        pad_b = pad_a + 1;
-                    }
+                    let label_chars = shrink_cell(&self.x_labels[i].chars, width, Overflow::Truncate)
+                        .into_iter()
+                        .next()
+                        .unwrap_or_default();
+                    let diff = width + 2 - chars_display_width(&label_chars);
+                    let (pad_a, pad_b) = align_padding(self.alignment_for_column(i), diff);
                     out.push_char_rep(' ', pad_a);
-                    out.push_colored_string(&self.x_labels[i]);
+                    out.chars.extend_from_slice(&label_chars);
                     out.push_char_rep(' ', pad_b);
-                    out.push_char_c('│', self.line_color);
+                    out.push_char_c(self.borders.vertical, self.line_color);
                 }
                 out.push_char('\n');
                 if self.draw_y_labels {
-                    out.push_char_rep_c('─', self.line_color, label_width + 1);
-                    out.push_char_c('╆', self.line_color);
+                    out.push_char_rep_c(self.borders.horizontal, self.line_color, label_width + 1);
+                    out.push_char_c(self.borders.header_left, self.line_color);
                 } else {
-                    out.push_char_c('┢', self.line_color);
+                    out.push_char_c(self.borders.header_left_no_y_labels, self.line_color);
                 }
                 for i in 0..self.grid.len() {
                     let width;
@@ -291,20 +904,20 @@ pub mod grid_print {
                     } else {
                         width = column_widths[i];
                     }
-                    out.push_char_rep_c('━', self.line_color, width + 2);
+                    out.push_char_rep_c(self.borders.horizontal_heavy, self.line_color, width + 2);
                     if i == self.grid.len() - 1 {
-                        out.push_char_c('┪', self.line_color);
+                        out.push_char_c(self.borders.header_right, self.line_color);
                     } else {
-                        out.push_char_c('┿', self.line_color);
+                        out.push_char_c(self.borders.header_mid, self.line_color);
                     }
                 }
                 out.push_char('\n');
             } else {
                 if self.draw_y_labels {
-                    out.push_char_rep_c('─', self.line_color, label_width + 1);
-                    out.push_char_c('┲', self.line_color);
+                    out.push_char_rep_c(self.borders.horizontal, self.line_color, label_width + 1);
+                    out.push_char_c(self.borders.top_left_with_y_labels, self.line_color);
                 } else {
-                    out.push_char_c('┏', self.line_color);
+                    out.push_char_c(self.borders.top_left, self.line_color);
                 }
                 for i in 0..self.grid.len() {
                     let width;
@@ -313,11 +926,11 @@ pub mod grid_print {
                     } else {
                         width = column_widths[i];
                     }
-                    out.push_char_rep_c('━', self.line_color, width + 2);
+                    out.push_char_rep_c(self.borders.horizontal_heavy, self.line_color, width + 2);
                     if i == self.grid.len() - 1 {
-                        out.push_char_c('┓', self.line_color);
+                        out.push_char_c(self.borders.top_right, self.line_color);
                     } else {
-                        out.push_char_c('┯', self.line_color);
+                        out.push_char_c(self.borders.top_mid, self.line_color);
                     }
                 }
                 out.push_char('\n');
@@ -325,43 +938,58 @@ pub mod grid_print {
 
             // Draw the rows.
             for y in 0..self.grid[0].len() {
-                if self.draw_y_labels {
-                    let pad_a = label_width - self.y_labels[y].chars.len();
-                    out.push_char_rep(' ', pad_a);
-                    out.push_colored_string(&self.y_labels[y]);
-                    out.push_char(' ');
-                }
-                out.push_char_c('┃', self.line_color);
+                let mut row_lines: Vec<Vec<Vec<ColoredChar>>> = Vec::with_capacity(self.grid.len());
                 for x in 0..self.grid.len() {
-                    let width;
-                    if self.static_column_width {
-                        width = largest_width;
-                    } else {
-                        width = column_widths[x];
-                    }
-                    let diff = width + 2 - self.grid[x][y].chars.len();
-                    let pad_a = diff / 2;
-                    let pad_b;
-                    if diff % 2 == 0 {
-                        pad_b = pad_a;
+                    let width = if self.static_column_width {
+                        largest_width
                     } else {
-                        pad_b = pad_a + 1;
+                        column_widths[x]
+                    };
+                    row_lines.push(shrink_cell(&self.grid[x][y].chars, width, self.overflow));
+                }
+                let line_count = row_lines.iter().map(|l| l.len()).max().unwrap_or(1).max(1);
+                for li in 0..line_count {
+                    if self.draw_y_labels {
+                        if li == 0 {
+                            let pad_a = label_width - self.y_labels[y].display_width();
+                            out.push_char_rep(' ', pad_a);
+                            out.push_colored_string(&self.y_labels[y]);
+                            out.push_char(' ');
+                        } else {
+                            out.push_char_rep(' ', label_width + 1);
+                        }
                     }
-                    out.push_char_rep(' ', pad_a);
-                    out.push_colored_string(&self.grid[x][y]);
-                    out.push_char_rep(' ', pad_b);
-                    if x < self.grid.len() - 1 {
-                        out.push_char_c('│', self.line_color);
+                    out.push_char_c(self.borders.vertical_heavy, self.line_color);
+                    for x in 0..self.grid.len() {
+                        let width = if self.static_column_width {
+                            largest_width
+                        } else {
+                            column_widths[x]
+                        };
+                        let empty = vec![];
+                        let cell_line = row_lines[x].get(li).unwrap_or(&empty);
+                        let diff = width + 2 - chars_display_width(cell_line);
+                        let (pad_a, pad_b) = align_padding(self.alignment_for_column(x), diff);
+                        out.push_char_rep(' ', pad_a);
+                        out.chars.extend_from_slice(cell_line);
+                        out.push_char_rep(' ', pad_b);
+                        if x < self.grid.len() - 1 {
+                            out.push_char_c(self.borders.vertical, self.line_color);
+                        }
                     }
+                    out.push_char_c(self.borders.vertical_heavy, self.line_color);
+                    out.push_char('\n');
                 }
-                out.push_char_c('┃', self.line_color);
-                out.push_char('\n');
                 if y < self.grid[0].len() - 1 {
                     if self.draw_y_labels {
-                        out.push_char_rep_c('─', self.line_color, label_width + 1);
-                        out.push_char_c('╂', self.line_color);
+                        out.push_char_rep_c(
+                            self.borders.horizontal,
+                            self.line_color,
+                            label_width + 1,
+                        );
+                        out.push_char_c(self.borders.row_left, self.line_color);
                     } else {
-                        out.push_char_c('┠', self.line_color);
+                        out.push_char_c(self.borders.row_left_no_y_labels, self.line_color);
                     }
                     for x in 0..self.grid.len() {
                         let width;
@@ -370,11 +998,11 @@ pub mod grid_print {
                         } else {
                             width = column_widths[x];
                         }
-                        out.push_char_rep_c('─', self.line_color, width + 2);
+                        out.push_char_rep_c(self.borders.horizontal, self.line_color, width + 2);
                         if x == self.grid.len() - 1 {
-                            out.push_char_c('┨', self.line_color);
+                            out.push_char_c(self.borders.row_right, self.line_color);
                         } else {
-                            out.push_char_c('┼', self.line_color);
+                            out.push_char_c(self.borders.row_mid, self.line_color);
                         }
                     }
                     out.push_char('\n');
@@ -383,10 +1011,10 @@ pub mod grid_print {
 
             // Draw the bottom border.
             if self.draw_y_labels {
-                out.push_char_rep_c('─', self.line_color, label_width + 1);
-                out.push_char_c('┺', self.line_color);
+                out.push_char_rep_c(self.borders.horizontal, self.line_color, label_width + 1);
+                out.push_char_c(self.borders.bottom_left, self.line_color);
             } else {
-                out.push_char_c('┗', self.line_color);
+                out.push_char_c(self.borders.bottom_left_no_y_labels, self.line_color);
             }
             for i in 0..self.grid.len() {
                 let width;
@@ -395,14 +1023,98 @@ pub mod grid_print {
                 } else {
                     width = column_widths[i];
                 }
-                out.push_char_rep_c('━', self.line_color, width + 2);
+                out.push_char_rep_c(self.borders.horizontal_heavy, self.line_color, width + 2);
                 if i == self.grid.len() - 1 {
-                    out.push_char_c('┛', self.line_color);
+                    out.push_char_c(self.borders.bottom_right, self.line_color);
                 } else {
-                    out.push_char_c('┷', self.line_color);
+                    out.push_char_c(self.borders.bottom_mid, self.line_color);
                 }
             }
-            out.print(&mut bufwtr, &mut buffer);
+            out.write_to(w);
+        }
+
+        /// Renders the grid to stdout, suppressing color when stdout isn't a
+        /// TTY (e.g. when piped).
+        pub fn print(&self) {
+            use std::io::IsTerminal;
+            let color_choice = if std::io::stdout().is_terminal() {
+                ColorChoice::Auto
+            } else {
+                ColorChoice::Never
+            };
+            let bufwtr = BufferWriter::stdout(color_choice);
+            let mut buffer = bufwtr.buffer();
+            self.render_to(&mut buffer);
+            bufwtr.print(&buffer).unwrap();
+        }
+
+        /// Renders the grid into a `String`, honoring `color_choice` for
+        /// whether ANSI escape codes are embedded in the output.
+        pub fn to_string(&self, color_choice: ColorChoice) -> String {
+            let mut buffer = match color_choice {
+                ColorChoice::Never => termcolor::Buffer::no_color(),
+                ColorChoice::Always | ColorChoice::AlwaysAnsi | ColorChoice::Auto => {
+                    termcolor::Buffer::ansi()
+                }
+            };
+            self.render_to(&mut buffer);
+            String::from_utf8_lossy(buffer.as_slice()).into_owned()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn longest_line_width(rendered: &str) -> usize {
+            rendered.lines().map(display_width).max().unwrap_or(0)
+        }
+
+        #[test]
+        fn display_width_ascii_is_char_count() {
+            assert_eq!(display_width("hello"), 5);
+        }
+
+        #[test]
+        fn display_width_counts_wide_codepoints_as_two() {
+            assert_eq!(display_width("中"), 2);
+            assert_eq!(display_width("中文"), 4);
+        }
+
+        #[test]
+        fn display_width_counts_combining_marks_as_zero() {
+            // "e" followed by U+0301 COMBINING ACUTE ACCENT.
+            assert_eq!(display_width("e\u{0301}"), 1);
+        }
+
+        #[test]
+        fn truncate_respects_max_width() {
+            let grid = Grid::new()
+                .set_draw_x_labels(false)
+                .set_draw_y_labels(false)
+                .set_grid(vec![
+                    vec![ColoredString::from("a very long first column value")],
+                    vec![ColoredString::from("a very long second column value")],
+                ])
+                .set_max_width(20)
+                .set_overflow(Overflow::Truncate);
+            let rendered = grid.to_string(ColorChoice::Never);
+            assert!(longest_line_width(&rendered) <= 20);
+        }
+
+        #[test]
+        fn wrap_respects_max_width() {
+            let grid = Grid::new()
+                .set_x_labels(vec![ColoredString::from("x1"), ColoredString::from("x2")])
+                .set_y_labels(vec![ColoredString::from("y1")])
+                .set_grid(vec![
+                    vec![ColoredString::from("a very long first column value")],
+                    vec![ColoredString::from("a very long second column value")],
+                ])
+                .set_max_width(24)
+                .set_overflow(Overflow::Wrap);
+            let rendered = grid.to_string(ColorChoice::Never);
+            assert!(longest_line_width(&rendered) <= 24);
         }
     }
 }